@@ -1,34 +1,124 @@
-//! Minimal draft pallet skeleton describing Temporal Lock anchoring for LUXBIN
-//! NOTE: This is a non-compiling, illustrative draft. Use as a starting point.
+//! Temporal Lock anchoring for LUXBIN.
+//!
+//! Anchors RSW time-lock puzzles that unlock via on-chain Wesolowski VDF proofs,
+//! and Groth16-verified memory roots that clients can prove membership against.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch::DispatchResult};
-use frame_system::ensure_signed;
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult, ensure,
+    traits::Get, weights::Weight,
+};
+use frame_system::offchain::{SendTransactionTypes, SubmitTransaction};
+use frame_system::{ensure_none, ensure_root, ensure_signed};
+use sp_runtime::offchain::storage::StorageValueRef;
+use sp_runtime::traits::{SaturatedConversion, Saturating};
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
+};
+use sp_std::vec::Vec;
 
-pub trait Config: frame_system::Config {
+/// A pending time-lock puzzle anchored on chain.
+///
+/// The puzzle is the classic Rivest-Shamir-Wagner construction `y = x^(2^t) mod N`.
+/// Rather than the squarings themselves we keep the parameters needed to check a
+/// Wesolowski VDF proof once the lock matures.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, sp_runtime::RuntimeDebug)]
+pub struct TemporalLock<BlockNumber> {
+    /// RSA-style modulus `N`, big-endian.
+    pub modulus: Vec<u8>,
+    /// Puzzle base `x`.
+    pub base: [u8; 32],
+    /// Number of squarings `t` (the puzzle computes `x^(2^t)`).
+    pub exponent_log2: u64,
+    /// Earliest block at which a reveal is accepted.
+    pub reveal_block: BlockNumber,
+}
+
+pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+    /// Upper bound on how far in the future a lock may be set to reveal.
+    type MaxLockDuration: Get<Self::BlockNumber>;
+    /// How long a matured lock survives before it is expired, giving the reveal
+    /// path (and the offchain worker) a window to open it.
+    type GracePeriod: Get<Self::BlockNumber>;
+    /// Maximum number of locks that may expire at the same block, bounding the
+    /// work `on_initialize` performs per block.
+    type MaxLocksPerBlock: Get<u32>;
+}
+
+/// Offchain solver progress for a single lock, checkpointed in local storage so
+/// a long `t` can be worked through across several blocks.
+#[derive(Encode, Decode, Clone, Default)]
+struct SolverCheckpoint {
+    /// Number of squarings already applied.
+    done: u64,
+    /// Current accumulator `r`, big-endian.
+    r: Vec<u8>,
 }
 
+/// Maximum number of squarings a single offchain run performs before
+/// checkpointing and yielding.
+const MAX_SQUARINGS_PER_RUN: u64 = 4096;
+
+/// Weight charged per lock cleared in `on_initialize` (one read, one write, one event).
+const EXPIRY_WEIGHT_PER_LOCK: Weight = 20_000;
+
 decl_storage! {
     trait Store for Module<T: Config> as TemporalOnchain {
-        // Map account -> Optional temporal lock (encoded bytes)
-        TemporalLocks get(fn temporal_locks): map hasher(blake2_128_concat) T::AccountId => Option<[u8;32]>;
-        // Anchored memory roots
-        MemoryRoots get(fn memory_roots): map hasher(blake2_128_concat) T::Hash => Option<[u8;32]>;
+        // Map account -> pending temporal lock.
+        TemporalLocks get(fn temporal_locks): map hasher(blake2_128_concat) T::AccountId => Option<TemporalLock<T::BlockNumber>>;
+        // Secondary index: accounts whose lock expires at a given block (maturity + grace).
+        LocksByBlock get(fn locks_by_block): map hasher(twox_64_concat) T::BlockNumber => Vec<T::AccountId>;
+        // Monotonically increasing counter of anchored roots.
+        RootIndex get(fn root_index): u64;
+        // Sequential log of anchored roots: index -> (block, root).
+        RootLog get(fn root_log): map hasher(twox_64_concat) u64 => Option<(T::BlockNumber, [u8;32])>;
+        // Direct index of the most recent root anchored at a block, for O(1) lookup.
+        RootIndexByBlock get(fn root_index_by_block): map hasher(twox_64_concat) T::BlockNumber => Option<u64>;
+        // Groth16 verifying key used to check proofs bound to anchored roots.
+        MemoryVerifyingKey get(fn memory_verifying_key): Option<Vec<u8>>;
     }
 }
 
 decl_event! (
     pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
         TemporalLockSubmitted(AccountId, u64),
+        /// A matured lock was opened; carries the target and the revealed `y`.
+        TemporalLockRevealed(AccountId, Vec<u8>),
+        /// A matured lock passed its grace period without being opened and was cleared.
+        TemporalLockExpired(AccountId),
         MemoryRootAnchored(AccountId),
+        /// A leaf was proven to belong to an anchored memory root.
+        MemoryLeafVerified(AccountId, [u8; 32]),
     }
 );
 
 decl_error! {
     pub enum Error for Module<T: Config> {
-        TooLarge,
+        /// No lock is registered for the target account.
+        NoLock,
+        /// The reveal was submitted before the lock's `reveal_block`.
+        TooEarly,
+        /// The supplied modulus is empty, zero, one or even.
+        MalformedModulus,
+        /// The Wesolowski proof did not verify against the stored puzzle.
+        InvalidProof,
+        /// The requested reveal delay exceeds `MaxLockDuration`.
+        LockTooLong,
+        /// Too many locks already expire at the requested block.
+        TooManyLocks,
+        /// No memory verifying key has been configured.
+        VerifyingKeyNotSet,
+        /// The stored or supplied verifying key could not be decoded.
+        MalformedVerifyingKey,
+        /// The Groth16 proof could not be decoded.
+        MalformedProof,
+        /// No memory root is anchored for the requested block.
+        RootNotFound,
+        /// The Merkle path did not fold to the anchored root.
+        BadProof,
     }
 }
 
@@ -36,23 +126,786 @@ decl_module! {
     pub struct Module<T: Config> for enum Call where origin: T::Origin {
         fn deposit_event() = default;
 
+        /// Drain locks scheduled to expire at this block, clearing them from storage.
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            // The per-block bucket is bounded at submission time by `MaxLocksPerBlock`,
+            // so the drain and its weight are bounded too.
+            let expiring = <LocksByBlock<T>>::take(n);
+            let count = expiring.len() as Weight;
+            for who in expiring {
+                <TemporalLocks<T>>::remove(&who);
+                Self::deposit_event(RawEvent::TemporalLockExpired(who));
+            }
+            count.saturating_mul(EXPIRY_WEIGHT_PER_LOCK)
+        }
+
+        /// Work through matured but unrevealed locks off chain and submit reveals.
+        fn offchain_worker(block: T::BlockNumber) {
+            for (target, lock) in <TemporalLocks<T>>::iter() {
+                if lock.reveal_block > block {
+                    continue;
+                }
+                if let Some((solution, proof)) = Self::advance_solver(&target, &lock) {
+                    let call = Call::reveal_temporal_lock(target, solution, proof);
+                    let _ = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
+                }
+            }
+        }
+
         #[weight = 10_000]
-        pub fn submit_temporal_lock(origin, target: T::AccountId, initial_hash: [u8;32], reveal_time: u64) -> DispatchResult {
+        pub fn submit_temporal_lock(origin, target: T::AccountId, modulus: Vec<u8>, base: [u8;32], exponent_log2: u64, reveal_time: u64) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            // store only the initial hash as anchor; full puzzle verification off-chain
-            <TemporalLocks<T>>::insert(&target, Some(initial_hash));
+            ensure!(Self::modulus_is_valid(&modulus), Error::<T>::MalformedModulus);
+            let now = <frame_system::Module<T>>::block_number();
+            // Bound the raw duration first so the block-number arithmetic below cannot overflow.
+            let duration = reveal_time.saturated_into::<T::BlockNumber>();
+            ensure!(duration <= T::MaxLockDuration::get(), Error::<T>::LockTooLong);
+            let reveal_block = now.saturating_add(duration);
+            // Expire strictly after the reveal window so a matured lock survives long
+            // enough to actually be opened.
+            let expire_block = reveal_block.saturating_add(T::GracePeriod::get());
+            // A resubmission overwrites the lock, so drop the target from its previous
+            // expiry bucket first — otherwise the old entry expires the new lock early.
+            if let Some(old) = <TemporalLocks<T>>::get(&target) {
+                let old_expire = old.reveal_block.saturating_add(T::GracePeriod::get());
+                <LocksByBlock<T>>::mutate(old_expire, |accounts| accounts.retain(|a| a != &target));
+            }
+            ensure!(
+                (<LocksByBlock<T>>::decode_len(expire_block).unwrap_or(0) as u32) < T::MaxLocksPerBlock::get(),
+                Error::<T>::TooManyLocks,
+            );
+            let lock = TemporalLock { modulus, base, exponent_log2, reveal_block };
+            <TemporalLocks<T>>::insert(&target, Some(lock));
+            <LocksByBlock<T>>::mutate(expire_block, |accounts| accounts.push(target.clone()));
             Self::deposit_event(RawEvent::TemporalLockSubmitted(who, reveal_time));
             Ok(())
         }
 
+        /// Open a matured lock by supplying a Wesolowski VDF proof of `y = x^(2^t) mod N`.
+        ///
+        /// Submitted unsigned by the offchain worker; `ValidateUnsigned` re-runs the
+        /// proof check before the transaction is admitted to the pool.
+        #[weight = 100_000]
+        pub fn reveal_temporal_lock(origin, target: T::AccountId, solution: Vec<u8>, proof: Vec<u8>) -> DispatchResult {
+            ensure_none(origin)?;
+            let lock = <TemporalLocks<T>>::get(&target).ok_or(Error::<T>::NoLock)?;
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(now >= lock.reveal_block, Error::<T>::TooEarly);
+            ensure!(Self::verify_wesolowski(&lock, &solution, &proof), Error::<T>::InvalidProof);
+            <TemporalLocks<T>>::remove(&target);
+            let expire_block = lock.reveal_block.saturating_add(T::GracePeriod::get());
+            <LocksByBlock<T>>::mutate(expire_block, |accounts| accounts.retain(|a| a != &target));
+            Self::deposit_event(RawEvent::TemporalLockRevealed(target, solution));
+            Ok(())
+        }
+
+        /// Install the Groth16 verifying key used by `attest_memory_root_with_proof`.
         #[weight = 10_000]
-        pub fn attest_memory_root(origin, root: [u8;32]) -> DispatchResult {
+        pub fn set_memory_vk(origin, vk: Vec<u8>) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(groth16::VerifyingKey::decode(&vk).is_some(), Error::<T>::MalformedVerifyingKey);
+            <MemoryVerifyingKey>::put(vk);
+            Ok(())
+        }
+
+        /// Anchor a memory root only if a Groth16 proof binds it to committed data.
+        #[weight = 100_000]
+        pub fn attest_memory_root_with_proof(origin, root: [u8;32], proof: Vec<u8>, public_inputs: Vec<[u8;32]>) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            let key = <frame_system::Module<T>>::block_number();
-            // naive store by block number hash
-            <MemoryRoots<T>>::insert(<T as frame_system::Config>::Hashing::hash_of(&key), Some(root));
+            let vk_bytes = <MemoryVerifyingKey>::get().ok_or(Error::<T>::VerifyingKeyNotSet)?;
+            let vk = groth16::VerifyingKey::decode(&vk_bytes).ok_or(Error::<T>::MalformedVerifyingKey)?;
+            let p = groth16::Proof::decode(&proof).ok_or(Error::<T>::MalformedProof)?;
+            // The root is bound as the first public input of the circuit, reduced
+            // into the scalar field so any canonical 32-byte hash is accepted.
+            let mut inputs = Vec::with_capacity(public_inputs.len() + 1);
+            inputs.push(groth16::scalar_from_root(&root));
+            for pi in &public_inputs {
+                inputs.push(groth16::scalar_from_bytes(pi).ok_or(Error::<T>::InvalidProof)?);
+            }
+            ensure!(groth16::verify(&vk, &p, &inputs), Error::<T>::InvalidProof);
+            Self::anchor_root(root);
             Self::deposit_event(RawEvent::MemoryRootAnchored(who));
             Ok(())
         }
+
+        /// Prove that `leaf` is included in the memory root anchored at `root_block`.
+        #[weight = 10_000 + (proof.len() as Weight).saturating_mul(2_000)]
+        pub fn verify_memory_inclusion(origin, root_block: T::BlockNumber, leaf: [u8;32], proof: Vec<([u8;32], bool)>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let root = Self::root_at_block(root_block).ok_or(Error::<T>::RootNotFound)?;
+            ensure!(merkle_root_from_proof(leaf, &proof) == root, Error::<T>::BadProof);
+            Self::deposit_event(RawEvent::MemoryLeafVerified(who, leaf));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Append a root to the sequential log under the next index.
+    fn anchor_root(root: [u8; 32]) {
+        let index = <RootIndex>::get();
+        let block = <frame_system::Module<T>>::block_number();
+        <RootLog<T>>::insert(index, (block, root));
+        <RootIndexByBlock<T>>::insert(block, index);
+        <RootIndex>::put(index + 1);
+    }
+
+    /// Look up the most recent memory root anchored at a given block, if any.
+    fn root_at_block(block: T::BlockNumber) -> Option<[u8; 32]> {
+        let index = <RootIndexByBlock<T>>::get(block)?;
+        <RootLog<T>>::get(index).map(|(_, root)| root)
+    }
+
+    /// Every root anchored in the inclusive block range `[from, to]`, in index order.
+    ///
+    /// Public helper for syncing clients. The log grows monotonically in
+    /// block order, so we binary-search the first matching index and walk forward
+    /// rather than scanning and sorting the whole map.
+    pub fn roots_in_range(
+        from: T::BlockNumber,
+        to: T::BlockNumber,
+    ) -> Vec<(u64, T::BlockNumber, [u8; 32])> {
+        let total = <RootIndex>::get();
+        if from > to {
+            return Vec::new();
+        }
+        let mut out = Vec::new();
+        let mut i = Self::first_index_at_or_after(from, total);
+        while i < total {
+            match <RootLog<T>>::get(i) {
+                Some((b, root)) if b <= to => out.push((i, b, root)),
+                _ => break,
+            }
+            i += 1;
+        }
+        out
+    }
+
+    /// Binary-search the smallest index whose anchoring block is `>= block`.
+    fn first_index_at_or_after(block: T::BlockNumber, total: u64) -> u64 {
+        let (mut lo, mut hi) = (0u64, total);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match <RootLog<T>>::get(mid) {
+                Some((b, _)) if b < block => lo = mid + 1,
+                _ => hi = mid,
+            }
+        }
+        lo
+    }
+
+    /// Advance the checkpointed sequential squaring for one lock.
+    ///
+    /// Returns `Some((y, proof))` once `y = x^(2^t) mod N` is complete, or `None`
+    /// while more rounds are needed — progress is persisted in local storage.
+    fn advance_solver(
+        target: &T::AccountId,
+        lock: &TemporalLock<T::BlockNumber>,
+    ) -> Option<(Vec<u8>, Vec<u8>)> {
+        let n = biguint::from_be_bytes(&lock.modulus);
+        if biguint::is_zero(&n) {
+            return None;
+        }
+        let t = lock.exponent_log2;
+
+        let mut key = b"temporal_onchain::solver/".to_vec();
+        key.extend_from_slice(&target.encode());
+        let store = StorageValueRef::persistent(&key);
+
+        let mut cp = store.get::<SolverCheckpoint>().ok().flatten().unwrap_or_else(|| {
+            let (_, r) = biguint::divmod(&biguint::from_be_bytes(&lock.base), &n);
+            SolverCheckpoint { done: 0, r: biguint::to_be_bytes(&r) }
+        });
+
+        let mut r = biguint::from_be_bytes(&cp.r);
+        let steps = core::cmp::min(MAX_SQUARINGS_PER_RUN, t.saturating_sub(cp.done));
+        for _ in 0..steps {
+            r = biguint::modmul(&r, &r, &n);
+        }
+        cp.done += steps;
+        cp.r = biguint::to_be_bytes(&r);
+
+        if cp.done < t {
+            store.set(&cp);
+            return None;
+        }
+
+        // Sequential squaring done: derive the challenge prime and build the proof.
+        let y_bytes = biguint::to_be_bytes(&r);
+        let x = biguint::from_be_bytes(&lock.base);
+        let mut data = Vec::with_capacity(lock.base.len() + y_bytes.len() + 8);
+        data.extend_from_slice(&lock.base);
+        data.extend_from_slice(&y_bytes);
+        data.extend_from_slice(&t.to_le_bytes());
+        let l = biguint::next_prime(biguint::from_be_bytes(&sp_io::hashing::blake2_256(&data)));
+        let pi = biguint::wesolowski_proof(&x, t, &l, &n);
+        store.clear();
+        Some((y_bytes, biguint::to_be_bytes(&pi)))
+    }
+
+    /// A usable modulus must be non-empty and represent an odd integer greater than one.
+    fn modulus_is_valid(modulus: &[u8]) -> bool {
+        let n = biguint::from_be_bytes(modulus);
+        !biguint::is_zero(&n)
+            && biguint::cmp(&n, &biguint::from_u64(1)) == sp_std::cmp::Ordering::Greater
+            && biguint::is_odd(&n)
+    }
+
+    /// Verify the Wesolowski proof for a stored lock.
+    fn verify_wesolowski(lock: &TemporalLock<T::BlockNumber>, solution: &[u8], proof: &[u8]) -> bool {
+        wesolowski_holds(&lock.modulus, &lock.base, lock.exponent_log2, solution, proof)
+    }
+}
+
+/// Verify `proof^l * x^r == y (mod N)` where `l = next_prime(H(x || y || t))`
+/// and `r = 2^t mod l`, the Wesolowski check for the RSW puzzle.
+fn wesolowski_holds(modulus: &[u8], base: &[u8; 32], exponent_log2: u64, solution: &[u8], proof: &[u8]) -> bool {
+    let n = biguint::from_be_bytes(modulus);
+    if biguint::is_zero(&n) {
+        return false;
+    }
+    let x = biguint::from_be_bytes(base);
+    let y = biguint::from_be_bytes(solution);
+    let pi = biguint::from_be_bytes(proof);
+
+    let mut data = Vec::with_capacity(base.len() + solution.len() + 8);
+    data.extend_from_slice(base);
+    data.extend_from_slice(solution);
+    data.extend_from_slice(&exponent_log2.to_le_bytes());
+    let l = biguint::next_prime(biguint::from_be_bytes(&sp_io::hashing::blake2_256(&data)));
+
+    let r = biguint::modpow(&biguint::from_u64(2), &biguint::from_u64(exponent_log2), &l);
+    let lhs = biguint::modmul(
+        &biguint::modpow(&pi, &l, &n),
+        &biguint::modpow(&x, &r, &n),
+        &n,
+    );
+    let (_, y_mod) = biguint::divmod(&y, &n);
+    biguint::cmp(&lhs, &y_mod) == sp_std::cmp::Ordering::Equal
+}
+
+/// Fold a leaf up a Merkle path, returning the implied root.
+fn merkle_root_from_proof(leaf: [u8; 32], proof: &[([u8; 32], bool)]) -> [u8; 32] {
+    let mut h = leaf;
+    for (sibling, is_right) in proof {
+        let mut data = [0u8; 64];
+        if *is_right {
+            data[..32].copy_from_slice(&h);
+            data[32..].copy_from_slice(sibling);
+        } else {
+            data[..32].copy_from_slice(sibling);
+            data[32..].copy_from_slice(&h);
+        }
+        h = sp_io::hashing::blake2_256(&data);
+    }
+    h
+}
+
+impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
+    type Call = Call<T>;
+
+    fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+        if let Call::reveal_temporal_lock(target, solution, proof) = call {
+            let lock = match <TemporalLocks<T>>::get(target) {
+                Some(lock) => lock,
+                None => return InvalidTransaction::Stale.into(),
+            };
+            if <frame_system::Module<T>>::block_number() < lock.reveal_block {
+                return InvalidTransaction::Future.into();
+            }
+            if !Self::verify_wesolowski(&lock, solution, proof) {
+                return InvalidTransaction::BadProof.into();
+            }
+            ValidTransaction::with_tag_prefix("TemporalOnchain")
+                .priority(100)
+                .and_provides((target, solution))
+                .longevity(64)
+                .propagate(true)
+                .build()
+        } else {
+            InvalidTransaction::Call.into()
+        }
+    }
+}
+
+/// Groth16 proof verification over BLS12-381, following the bellman layout used
+/// by the zcash light-client stack. Points are stored in their compressed form:
+/// 48 bytes per G1 element and 96 bytes per G2 element.
+mod groth16 {
+    use bls12_381::{
+        multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, Gt, Scalar,
+    };
+    use sp_std::convert::TryInto;
+    use sp_std::vec::Vec;
+
+    /// A Groth16 verifying key: the fixed pairing elements plus the input commitment `IC`.
+    pub struct VerifyingKey {
+        pub alpha_g1: G1Affine,
+        pub beta_g2: G2Affine,
+        pub gamma_g2: G2Affine,
+        pub delta_g2: G2Affine,
+        pub ic: Vec<G1Affine>,
+    }
+
+    /// A Groth16 proof: `A` in G1, `B` in G2, `C` in G1.
+    pub struct Proof {
+        pub a: G1Affine,
+        pub b: G2Affine,
+        pub c: G1Affine,
+    }
+
+    fn read_g1(b: &[u8]) -> Option<G1Affine> {
+        let arr: [u8; 48] = b.try_into().ok()?;
+        Option::from(G1Affine::from_compressed(&arr))
+    }
+
+    fn read_g2(b: &[u8]) -> Option<G2Affine> {
+        let arr: [u8; 96] = b.try_into().ok()?;
+        Option::from(G2Affine::from_compressed(&arr))
+    }
+
+    pub fn scalar_from_bytes(b: &[u8; 32]) -> Option<Scalar> {
+        Option::from(Scalar::from_bytes(b))
+    }
+
+    /// Reduce an arbitrary 32-byte value (e.g. a hash-derived root) into the
+    /// scalar field instead of rejecting canonical values `>=` the field modulus.
+    pub fn scalar_from_root(b: &[u8; 32]) -> Scalar {
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(b);
+        Scalar::from_bytes_wide(&wide)
+    }
+
+    impl VerifyingKey {
+        /// Layout: `alpha_g1 || beta_g2 || gamma_g2 || delta_g2 || ic_len(u32 be) || ic[..]`.
+        pub fn decode(bytes: &[u8]) -> Option<VerifyingKey> {
+            let mut o = 0usize;
+            let alpha_g1 = read_g1(bytes.get(o..o + 48)?)?;
+            o += 48;
+            let beta_g2 = read_g2(bytes.get(o..o + 96)?)?;
+            o += 96;
+            let gamma_g2 = read_g2(bytes.get(o..o + 96)?)?;
+            o += 96;
+            let delta_g2 = read_g2(bytes.get(o..o + 96)?)?;
+            o += 96;
+            let len_bytes: [u8; 4] = bytes.get(o..o + 4)?.try_into().ok()?;
+            o += 4;
+            let n = u32::from_be_bytes(len_bytes) as usize;
+            let mut ic = Vec::with_capacity(n);
+            for _ in 0..n {
+                ic.push(read_g1(bytes.get(o..o + 48)?)?);
+                o += 48;
+            }
+            Some(VerifyingKey { alpha_g1, beta_g2, gamma_g2, delta_g2, ic })
+        }
+    }
+
+    impl Proof {
+        pub fn decode(bytes: &[u8]) -> Option<Proof> {
+            if bytes.len() != 48 + 96 + 48 {
+                return None;
+            }
+            let a = read_g1(&bytes[0..48])?;
+            let b = read_g2(&bytes[48..144])?;
+            let c = read_g1(&bytes[144..192])?;
+            Some(Proof { a, b, c })
+        }
+    }
+
+    /// Check `e(A, B) == e(alpha, beta) * e(vk_x, gamma) * e(C, delta)`, where
+    /// `vk_x = IC[0] + sum_i input_i * IC[i+1]`.
+    pub fn verify(vk: &VerifyingKey, proof: &Proof, inputs: &[Scalar]) -> bool {
+        if inputs.len() + 1 != vk.ic.len() {
+            return false;
+        }
+        let mut acc = G1Projective::from(vk.ic[0]);
+        for (i, s) in inputs.iter().enumerate() {
+            acc += vk.ic[i + 1] * *s;
+        }
+        let vk_x = G1Affine::from(acc);
+        let terms = [
+            (&proof.a, &G2Prepared::from(proof.b)),
+            (&(-vk.alpha_g1), &G2Prepared::from(vk.beta_g2)),
+            (&(-vk_x), &G2Prepared::from(vk.gamma_g2)),
+            (&(-proof.c), &G2Prepared::from(vk.delta_g2)),
+        ];
+        multi_miller_loop(&terms).final_exponentiation() == Gt::identity()
+    }
+}
+
+/// Minimal unsigned big-integer arithmetic for on-chain puzzle verification.
+///
+/// Numbers are little-endian `u32` limbs with no trailing zero limbs. This is
+/// deliberately small and allocation-friendly rather than fast; the heavy
+/// sequential squaring stays off chain and only the short Wesolowski check runs
+/// here.
+mod biguint {
+    use sp_std::cmp::Ordering;
+    use sp_std::vec;
+    use sp_std::vec::Vec;
+
+    pub type Big = Vec<u32>;
+
+    pub fn zero() -> Big {
+        vec![0]
+    }
+
+    pub fn from_u64(mut x: u64) -> Big {
+        if x == 0 {
+            return zero();
+        }
+        let mut v = Vec::new();
+        while x > 0 {
+            v.push((x & 0xffff_ffff) as u32);
+            x >>= 32;
+        }
+        v
+    }
+
+    pub fn from_be_bytes(bytes: &[u8]) -> Big {
+        let mut v = Vec::new();
+        let mut i = bytes.len();
+        while i > 0 {
+            let start = if i >= 4 { i - 4 } else { 0 };
+            let mut limb = 0u32;
+            for &b in &bytes[start..i] {
+                limb = (limb << 8) | b as u32;
+            }
+            v.push(limb);
+            i = start;
+        }
+        if v.is_empty() {
+            v.push(0);
+        }
+        normalize(&mut v);
+        v
+    }
+
+    pub fn to_be_bytes(a: &Big) -> Vec<u8> {
+        let len = effective_len(a);
+        if len == 0 {
+            return vec![0];
+        }
+        let mut out = Vec::with_capacity(len * 4);
+        for i in (0..len).rev() {
+            out.extend_from_slice(&a[i].to_be_bytes());
+        }
+        while out.len() > 1 && out[0] == 0 {
+            out.remove(0);
+        }
+        out
+    }
+
+    pub fn is_zero(a: &Big) -> bool {
+        a.iter().all(|&x| x == 0)
+    }
+
+    pub fn is_odd(a: &Big) -> bool {
+        a.first().map_or(false, |&x| x & 1 == 1)
+    }
+
+    fn normalize(a: &mut Big) {
+        while a.len() > 1 && *a.last().unwrap() == 0 {
+            a.pop();
+        }
+    }
+
+    fn effective_len(a: &Big) -> usize {
+        let mut l = a.len();
+        while l > 0 && a[l - 1] == 0 {
+            l -= 1;
+        }
+        l
+    }
+
+    pub fn cmp(a: &Big, b: &Big) -> Ordering {
+        let la = effective_len(a);
+        let lb = effective_len(b);
+        if la != lb {
+            return la.cmp(&lb);
+        }
+        for i in (0..la).rev() {
+            if a[i] != b[i] {
+                return a[i].cmp(&b[i]);
+            }
+        }
+        Ordering::Equal
+    }
+
+    pub fn add(a: &Big, b: &Big) -> Big {
+        let mut r = Vec::new();
+        let mut carry = 0u64;
+        let n = a.len().max(b.len());
+        for i in 0..n {
+            let av = *a.get(i).unwrap_or(&0) as u64;
+            let bv = *b.get(i).unwrap_or(&0) as u64;
+            let s = av + bv + carry;
+            r.push((s & 0xffff_ffff) as u32);
+            carry = s >> 32;
+        }
+        if carry > 0 {
+            r.push(carry as u32);
+        }
+        r
+    }
+
+    /// Requires `a >= b`.
+    pub fn sub(a: &Big, b: &Big) -> Big {
+        let mut r = Vec::new();
+        let mut borrow = 0i64;
+        for i in 0..a.len() {
+            let av = a[i] as i64;
+            let bv = *b.get(i).unwrap_or(&0) as i64;
+            let mut d = av - bv - borrow;
+            if d < 0 {
+                d += 1 << 32;
+                borrow = 1;
+            } else {
+                borrow = 0;
+            }
+            r.push(d as u32);
+        }
+        normalize(&mut r);
+        r
+    }
+
+    pub fn mul(a: &Big, b: &Big) -> Big {
+        let mut r = vec![0u32; a.len() + b.len()];
+        for i in 0..a.len() {
+            let mut carry = 0u64;
+            let av = a[i] as u64;
+            for j in 0..b.len() {
+                let idx = i + j;
+                let cur = r[idx] as u64 + av * (b[j] as u64) + carry;
+                r[idx] = (cur & 0xffff_ffff) as u32;
+                carry = cur >> 32;
+            }
+            let mut idx = i + b.len();
+            while carry > 0 {
+                let cur = r[idx] as u64 + carry;
+                r[idx] = (cur & 0xffff_ffff) as u32;
+                carry = cur >> 32;
+                idx += 1;
+            }
+        }
+        normalize(&mut r);
+        r
+    }
+
+    fn get_bit(a: &Big, i: usize) -> bool {
+        let limb = i / 32;
+        let bit = i % 32;
+        limb < a.len() && (a[limb] >> bit) & 1 == 1
+    }
+
+    fn set_bit(a: &mut Big, i: usize) {
+        let limb = i / 32;
+        let bit = i % 32;
+        while a.len() <= limb {
+            a.push(0);
+        }
+        a[limb] |= 1 << bit;
+    }
+
+    fn shl1(a: &mut Big) {
+        let mut carry = 0u32;
+        for x in a.iter_mut() {
+            let nc = *x >> 31;
+            *x = (*x << 1) | carry;
+            carry = nc;
+        }
+        if carry > 0 {
+            a.push(carry);
+        }
+    }
+
+    fn shr1(a: &mut Big) {
+        let mut carry = 0u32;
+        for i in (0..a.len()).rev() {
+            let nc = a[i] & 1;
+            a[i] = (a[i] >> 1) | (carry << 31);
+            carry = nc;
+        }
+        normalize(a);
+    }
+
+    /// Long division returning `(quotient, remainder)`; `m` must be non-zero.
+    pub fn divmod(a: &Big, m: &Big) -> (Big, Big) {
+        let mut q = vec![0u32; a.len()];
+        let mut r = zero();
+        for i in (0..a.len() * 32).rev() {
+            shl1(&mut r);
+            if get_bit(a, i) {
+                r[0] |= 1;
+            }
+            if cmp(&r, m) != Ordering::Less {
+                r = sub(&r, m);
+                set_bit(&mut q, i);
+            }
+        }
+        normalize(&mut q);
+        (q, r)
+    }
+
+    pub fn modmul(a: &Big, b: &Big, m: &Big) -> Big {
+        let (_, r) = divmod(&mul(a, b), m);
+        r
+    }
+
+    pub fn modpow(base: &Big, exp: &Big, m: &Big) -> Big {
+        if cmp(m, &from_u64(1)) != Ordering::Greater {
+            return zero();
+        }
+        let mut result = from_u64(1);
+        let (_, mut b) = divmod(base, m);
+        for i in 0..exp.len() * 32 {
+            if get_bit(exp, i) {
+                result = modmul(&result, &b, m);
+            }
+            b = modmul(&b, &b, m);
+        }
+        result
+    }
+
+    fn is_probable_prime(n: &Big) -> bool {
+        let two = from_u64(2);
+        match cmp(n, &two) {
+            Ordering::Less => return false,
+            Ordering::Equal => return true,
+            Ordering::Greater => {}
+        }
+        if !is_odd(n) {
+            return false;
+        }
+        let one = from_u64(1);
+        let nm1 = sub(n, &one);
+        let mut d = nm1.clone();
+        let mut s = 0u32;
+        while !is_odd(&d) {
+            shr1(&mut d);
+            s += 1;
+        }
+        for &wit in &[2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+            let a = from_u64(wit);
+            if cmp(&a, n) != Ordering::Less {
+                continue;
+            }
+            let mut x = modpow(&a, &d, n);
+            if cmp(&x, &one) == Ordering::Equal || cmp(&x, &nm1) == Ordering::Equal {
+                continue;
+            }
+            let mut composite = true;
+            for _ in 0..s.saturating_sub(1) {
+                x = modmul(&x, &x, n);
+                if cmp(&x, &nm1) == Ordering::Equal {
+                    composite = false;
+                    break;
+                }
+            }
+            if composite {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Compute the Wesolowski proof `pi = x^(2^t div l) mod N` without ever
+    /// materialising `2^t`, folding the long division into the squaring loop:
+    /// each round doubles the running remainder and multiplies in `x` when it wraps.
+    pub fn wesolowski_proof(x: &Big, t: u64, l: &Big, n: &Big) -> Big {
+        let one = from_u64(1);
+        let (_, xm) = divmod(x, n);
+        let mut rem = one.clone();
+        let mut pi = one.clone();
+        for _ in 0..t {
+            let mut two_r = rem.clone();
+            shl1(&mut two_r);
+            let wrapped = cmp(&two_r, l) != Ordering::Less;
+            rem = if wrapped { sub(&two_r, l) } else { two_r };
+            pi = modmul(&pi, &pi, n);
+            if wrapped {
+                pi = modmul(&pi, &xm, n);
+            }
+        }
+        pi
+    }
+
+    /// Map `n` to the smallest prime `>= max(n, 3)`, used to derive the
+    /// Wesolowski challenge prime `l`.
+    pub fn next_prime(mut n: Big) -> Big {
+        if cmp(&n, &from_u64(3)) == Ordering::Less {
+            return from_u64(3);
+        }
+        if !is_odd(&n) {
+            n = add(&n, &from_u64(1));
+        }
+        loop {
+            if is_probable_prime(&n) {
+                return n;
+            }
+            n = add(&n, &from_u64(2));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn biguint_divmod_and_modpow() {
+        let (q, r) = biguint::divmod(&biguint::from_u64(100), &biguint::from_u64(7));
+        assert_eq!(biguint::to_be_bytes(&q), vec![14]);
+        assert_eq!(biguint::to_be_bytes(&r), vec![2]);
+        // 2^10 mod 1000 == 24
+        let got = biguint::modpow(&biguint::from_u64(2), &biguint::from_u64(10), &biguint::from_u64(1000));
+        assert_eq!(biguint::to_be_bytes(&got), vec![24]);
+    }
+
+    #[test]
+    fn wesolowski_round_trip() {
+        // Small RSW puzzle: N = 11 * 13 = 143, x = 7, t = 8.
+        let n = biguint::from_u64(143);
+        let mut base = [0u8; 32];
+        base[31] = 7;
+        let t = 8u64;
+        let x = biguint::from_be_bytes(&base);
+
+        // y = x^(2^t) mod N by sequential squaring.
+        let (_, mut r) = biguint::divmod(&x, &n);
+        for _ in 0..t {
+            r = biguint::modmul(&r, &r, &n);
+        }
+        let solution = biguint::to_be_bytes(&r);
+
+        // Derive the challenge prime and build the matching proof.
+        let mut data = Vec::new();
+        data.extend_from_slice(&base);
+        data.extend_from_slice(&solution);
+        data.extend_from_slice(&t.to_le_bytes());
+        let l = biguint::next_prime(biguint::from_be_bytes(&sp_io::hashing::blake2_256(&data)));
+        let pi = biguint::wesolowski_proof(&x, t, &l, &n);
+        let proof = biguint::to_be_bytes(&pi);
+        let modulus = biguint::to_be_bytes(&n);
+
+        assert!(wesolowski_holds(&modulus, &base, t, &solution, &proof));
+
+        // A tampered solution must not verify.
+        let mut bad = solution.clone();
+        bad[0] ^= 0x01;
+        assert!(!wesolowski_holds(&modulus, &base, t, &bad, &proof));
+    }
+
+    #[test]
+    fn merkle_inclusion_success_and_failure() {
+        let leaf = [1u8; 32];
+        let sibling = [2u8; 32];
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(&leaf);
+        data[32..].copy_from_slice(&sibling);
+        let root = sp_io::hashing::blake2_256(&data);
+
+        assert_eq!(merkle_root_from_proof(leaf, &[(sibling, true)]), root);
+        assert_ne!(merkle_root_from_proof(leaf, &[([9u8; 32], true)]), root);
     }
 }